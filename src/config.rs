@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use quanta::{Clock, Instant};
+
+/// Configures how [`ProjectStats`](crate::stats::ProjectStats) (and
+/// [`AtomicProjectStats`](crate::stats::AtomicProjectStats)) bucket and
+/// evaluate spent budget.
+#[derive(Debug, Clone)]
+pub struct BudgetingConfig {
+    /// How long a project stays in its current over/under budget state
+    /// before it is allowed to flip again.
+    pub(crate) backoff_duration: Duration,
+
+    /// The sliding window over which spends are summed up.
+    pub(crate) budgeting_window: Duration,
+
+    /// The granularity at which spends are bucketed within `budgeting_window`.
+    pub(crate) bucket_duration: Duration,
+
+    /// The total budget allowed within `budgeting_window`.
+    pub(crate) allowed_budget: f64,
+
+    /// The number of buckets that fit into `budgeting_window`.
+    pub(crate) num_buckets: usize,
+
+    /// Whether budget is a flat cap per `budgeting_window`, or continuously
+    /// replenished like a token bucket.
+    pub(crate) mode: BudgetMode,
+
+    /// The source of time used to bucket and evaluate spends.
+    timer: Timer,
+}
+
+impl BudgetingConfig {
+    /// Creates a new configuration.
+    pub fn new(
+        backoff_duration: Duration,
+        budgeting_window: Duration,
+        bucket_duration: Duration,
+        allowed_budget: f64,
+    ) -> Self {
+        let num_buckets = ((budgeting_window.as_secs_f64() / bucket_duration.as_secs_f64()).ceil()
+            as usize)
+            .max(1);
+
+        Self {
+            backoff_duration,
+            budgeting_window,
+            bucket_duration,
+            allowed_budget,
+            num_buckets,
+            mode: BudgetMode::Window,
+            timer: Timer::default(),
+        }
+    }
+
+    /// Switches this configuration to a replenishing, token-bucket budget:
+    /// the balance accrues at `replenish_rate` units per second, up to a
+    /// burst capacity of `max_budget`, instead of comparing a sliding window
+    /// of spends against a flat `allowed_budget`.
+    ///
+    /// This is modeled on Chromium's `budget_service`, and suits callers
+    /// that want to express "X per second with some burst capacity" rather
+    /// than "X total per fixed window".
+    pub fn with_replenishing(mut self, replenish_rate: f64, max_budget: f64) -> Self {
+        self.mode = BudgetMode::Replenishing {
+            replenish_rate,
+            max_budget,
+        };
+        self
+    }
+
+    /// Overrides the [`Timer`] used to obtain the current time.
+    ///
+    /// This is primarily useful for tests, which want to drive time via a
+    /// [`quanta::Clock::mock`].
+    pub fn with_timer(mut self, timer: Timer) -> Self {
+        self.timer = timer;
+        self
+    }
+
+    /// Returns the current time, truncated down to the start of its bucket.
+    pub(crate) fn truncated_now(&self) -> Instant {
+        self.timer.truncated_now(self.bucket_duration)
+    }
+
+    /// Returns the current, untruncated time.
+    pub(crate) fn now(&self) -> Instant {
+        self.timer.now()
+    }
+
+    /// Returns the number of nanoseconds that have elapsed between this
+    /// config's [`Timer`] being created and `instant`.
+    ///
+    /// This gives [`AtomicProjectStats`](crate::stats::AtomicProjectStats) a
+    /// plain integer representation of time that fits into an `AtomicU64`.
+    pub(crate) fn nanos_since_start(&self, instant: Instant) -> u64 {
+        self.timer.nanos_since_start(instant)
+    }
+}
+
+/// How a [`BudgetingConfig`] determines whether a project is over budget.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BudgetMode {
+    /// Spends recorded within `budgeting_window` are summed and compared
+    /// against a flat `allowed_budget`.
+    Window,
+
+    /// Budget continuously replenishes at `replenish_rate` units per second,
+    /// capped at a burst capacity of `max_budget`, token-bucket style.
+    Replenishing { replenish_rate: f64, max_budget: f64 },
+}
+
+/// A source of time for a [`BudgetingConfig`].
+///
+/// Wraps a [`quanta::Clock`] so that tests can swap in a mocked clock while
+/// production code pays only the cost of a single TSC read.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    clock: Clock,
+    start: Instant,
+}
+
+impl Default for Timer {
+    fn default() -> Self {
+        Self::new(Clock::new())
+    }
+}
+
+impl Timer {
+    /// Creates a new [`Timer`] backed by the given [`quanta::Clock`].
+    pub fn new(clock: Clock) -> Self {
+        let start = clock.now();
+        Self { clock, start }
+    }
+
+    fn now(&self) -> Instant {
+        self.clock.now()
+    }
+
+    fn truncated_now(&self, bucket_duration: Duration) -> Instant {
+        let elapsed = self.now() - self.start;
+        let bucket_nanos = bucket_duration.as_nanos().max(1);
+        let truncated_nanos = (elapsed.as_nanos() / bucket_nanos) * bucket_nanos;
+        self.start + Duration::from_nanos(truncated_nanos as u64)
+    }
+
+    fn nanos_since_start(&self, instant: Instant) -> u64 {
+        (instant - self.start).as_nanos() as u64
+    }
+}
@@ -0,0 +1,4 @@
+//! Budget tracking for per-project (or per-anything) rate limiting.
+
+pub mod config;
+pub mod stats;
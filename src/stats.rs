@@ -1,9 +1,31 @@
 use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use quanta::Instant;
 
-use crate::config::BudgetingConfig;
+use crate::config::{BudgetMode, BudgetingConfig};
+
+/// The outcome of an atomic reserve-or-reject spend, as returned by
+/// [`ProjectStats::try_spend`] and [`AtomicProjectStats::try_spend`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpendOutcome {
+    /// Whether the spend was committed.
+    ///
+    /// A spend is only rejected if the project was already at or over
+    /// budget before this call; a call that pushes the project over budget
+    /// is still accepted, see `hit_zero`.
+    pub accepted: bool,
+
+    /// The budget remaining after this call. May be negative once the
+    /// project is over budget.
+    pub remaining: f64,
+
+    /// Whether this call is the one that pushed the project from under to
+    /// over budget.
+    pub hit_zero: bool,
+}
 
 /// Per-project (per-anything, really) budget tracking.
 ///
@@ -21,18 +43,44 @@ pub struct ProjectStats {
     backoff_deadline: Option<Instant>,
 
     /// The buckets that are used to keep track of the spent budget.
+    ///
+    /// Each entry is `(expiry, amount)`: `amount` stops counting towards the
+    /// window total once `expiry` is in the past. By default a spend expires
+    /// after `budgeting_window`, but `record_budget_spend_with_expiry` lets
+    /// an individual spend carry its own, different, expiration.
+    ///
+    /// Only used in [`BudgetMode::Window`].
     budget_buckets: VecDeque<(Instant, f64)>,
+
+    /// The currently available balance.
+    ///
+    /// Only used in [`BudgetMode::Replenishing`], where it starts out at
+    /// `max_budget` and is topped up over time at `replenish_rate`.
+    balance: f64,
+
+    /// The last time `balance` was topped up.
+    ///
+    /// Only used in [`BudgetMode::Replenishing`].
+    last_replenish: Instant,
 }
 
 impl ProjectStats {
     /// Create a new per-project tracker based on the given [`BudgetingConfig`].
     pub fn new(config: Arc<BudgetingConfig>) -> Self {
         let budget_buckets = VecDeque::with_capacity(config.num_buckets);
+        let balance = match config.mode {
+            BudgetMode::Window => 0.0,
+            BudgetMode::Replenishing { max_budget, .. } => max_budget,
+        };
+        let last_replenish = config.now();
+
         Self {
             config,
             exceeds_budget: false,
             backoff_deadline: None,
             budget_buckets,
+            balance,
+            last_replenish,
         }
     }
 
@@ -40,32 +88,188 @@ impl ProjectStats {
     ///
     /// This will also update internal state when checking.
     pub fn exceeds_budget(&mut self) -> bool {
-        self.update_aggregated_state(self.config.truncated_now())
+        match self.config.mode {
+            BudgetMode::Window => self.update_aggregated_state(self.config.truncated_now()),
+            BudgetMode::Replenishing { .. } => {
+                let now = self.config.now();
+                let projected_balance = self.replenished_balance(now);
+                self.transition(now, projected_balance < 0.0)
+            }
+        }
     }
 
     /// Records spent budget.
     ///
     /// This will also update internal state when checking.
     pub fn record_budget_spend(&mut self, spent_budget: f64) -> bool {
+        match self.config.mode {
+            BudgetMode::Window => self.record_budget_spend_windowed(spent_budget, None),
+            BudgetMode::Replenishing { .. } => self.record_budget_spend_replenishing(spent_budget),
+        }
+    }
+
+    /// Like `record_budget_spend`, but lets this particular spend expire
+    /// after `expires_in` instead of the configured `budgeting_window`.
+    ///
+    /// This suits mixed workloads where, say, an expensive batch operation
+    /// should count against the budget for much longer than a cheap request.
+    /// In [`BudgetMode::Replenishing`] there is no per-spend expiration, so
+    /// `expires_in` is ignored there and this behaves like `record_budget_spend`.
+    pub fn record_budget_spend_with_expiry(&mut self, spent_budget: f64, expires_in: Duration) -> bool {
+        match self.config.mode {
+            BudgetMode::Window => self.record_budget_spend_windowed(spent_budget, Some(expires_in)),
+            BudgetMode::Replenishing { .. } => self.record_budget_spend_replenishing(spent_budget),
+        }
+    }
+
+    fn record_budget_spend_windowed(&mut self, spent_budget: f64, expires_in: Option<Duration>) -> bool {
         let now = self.config.truncated_now();
+        self.push_bucket(now, expires_in, spent_budget);
+        self.update_aggregated_state(now)
+    }
 
-        if let Some(latest) = self.budget_buckets.front_mut() {
-            if latest.0 >= now {
-                latest.1 += spent_budget;
-            } else {
-                if self.budget_buckets.len() >= self.config.num_buckets {
-                    self.budget_buckets.pop_back();
+    /// Adds `amount` to the bucket that expires at `now + expires_in` (or
+    /// `now + budgeting_window` if `expires_in` is `None`), merging it into
+    /// the most recent bucket when that bucket shares the same, default,
+    /// expiry.
+    fn push_bucket(&mut self, now: Instant, expires_in: Option<Duration>, amount: f64) {
+        let expiry = now + expires_in.unwrap_or(self.config.budgeting_window);
+
+        if expires_in.is_none() {
+            if let Some(latest) = self.budget_buckets.front_mut() {
+                if latest.0 == expiry {
+                    latest.1 += amount;
+                    return;
                 }
-                self.budget_buckets.push_front((now, spent_budget));
             }
-        } else {
-            self.budget_buckets.push_front((now, spent_budget));
         }
 
-        self.update_aggregated_state(now)
+        if self.budget_buckets.len() >= self.config.num_buckets {
+            self.evict_one(now);
+        }
+        self.budget_buckets.push_front((expiry, amount));
+    }
+
+    /// Evicts a single bucket to make room for a new one.
+    ///
+    /// Buckets are no longer necessarily pushed in expiry order once
+    /// `record_budget_spend_with_expiry` is mixed in, so eviction can't just
+    /// pop the back of the queue: that bucket may carry a long, custom TTL
+    /// and not have expired yet. Prefer evicting an already-expired bucket;
+    /// if none has expired, fall back to the one expiring soonest, so we
+    /// never evict a bucket ahead of one that would expire sooner anyway.
+    fn evict_one(&mut self, now: Instant) {
+        let idx = self
+            .budget_buckets
+            .iter()
+            .position(|b| b.0 <= now)
+            .or_else(|| {
+                self.budget_buckets
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.0.partial_cmp(&b.0).unwrap())
+                    .map(|(i, _)| i)
+            });
+
+        if let Some(idx) = idx {
+            self.budget_buckets.remove(idx);
+        }
+    }
+
+    /// Atomically reserves `amount` against the budget, rejecting the spend
+    /// instead of recording it if the project is already at or over budget.
+    ///
+    /// Unlike `record_budget_spend`, this reports in one call whether the
+    /// spend was actually committed and how much headroom remains, so
+    /// admission-control callers don't need a separate follow-up check to
+    /// find out they've already overshot.
+    pub fn try_spend(&mut self, amount: f64) -> SpendOutcome {
+        match self.config.mode {
+            BudgetMode::Window => self.try_spend_windowed(amount),
+            BudgetMode::Replenishing { .. } => self.try_spend_replenishing(amount),
+        }
+    }
+
+    fn try_spend_windowed(&mut self, amount: f64) -> SpendOutcome {
+        let now = self.config.truncated_now();
+        let was_exceeding = self.exceeds_budget;
+        let current_total = self.windowed_total(now);
+
+        if current_total >= self.config.allowed_budget {
+            self.transition(now, true);
+            return SpendOutcome {
+                accepted: false,
+                remaining: self.config.allowed_budget - current_total,
+                hit_zero: false,
+            };
+        }
+
+        self.push_bucket(now, None, amount);
+        let new_total = current_total + amount;
+        let exceeds = new_total > self.config.allowed_budget;
+        self.transition(now, exceeds);
+
+        SpendOutcome {
+            accepted: true,
+            remaining: self.config.allowed_budget - new_total,
+            hit_zero: !was_exceeding && exceeds,
+        }
+    }
+
+    fn try_spend_replenishing(&mut self, amount: f64) -> SpendOutcome {
+        let now = self.config.now();
+        let was_exceeding = self.exceeds_budget;
+        let balance = self.replenished_balance(now);
+
+        if balance <= 0.0 {
+            self.balance = balance;
+            self.last_replenish = now;
+            self.transition(now, true);
+            return SpendOutcome {
+                accepted: false,
+                remaining: balance,
+                hit_zero: false,
+            };
+        }
+
+        let new_balance = balance - amount;
+        self.balance = new_balance;
+        self.last_replenish = now;
+        let exceeds = new_balance < 0.0;
+        self.transition(now, exceeds);
+
+        SpendOutcome {
+            accepted: true,
+            remaining: new_balance,
+            hit_zero: !was_exceeding && exceeds,
+        }
     }
 
-    /// Checks whether all of the buckets are outside the current `budgeting_window`.
+    fn record_budget_spend_replenishing(&mut self, spent_budget: f64) -> bool {
+        let now = self.config.now();
+        self.balance = self.replenished_balance(now);
+        self.last_replenish = now;
+        self.balance -= spent_budget;
+
+        self.transition(now, self.balance < 0.0)
+    }
+
+    /// Returns what `balance` would be if it were topped up as of `now`,
+    /// without committing the update.
+    fn replenished_balance(&self, now: Instant) -> f64 {
+        let BudgetMode::Replenishing {
+            replenish_rate,
+            max_budget,
+        } = self.config.mode
+        else {
+            return self.balance;
+        };
+
+        let elapsed = (now - self.last_replenish).as_secs_f64();
+        (self.balance + replenish_rate * elapsed).min(max_budget)
+    }
+
+    /// Checks whether every recorded spend has expired.
     ///
     /// This means that these stats can be cleaned up.
     pub fn is_stale(&self, now: Instant) -> bool {
@@ -76,14 +280,37 @@ impl ProjectStats {
             }
         }
 
-        let lowest_time = now - self.config.budgeting_window;
-        self.budget_buckets.iter().any(|b| b.0 >= lowest_time)
+        match self.config.mode {
+            BudgetMode::Window => !self.budget_buckets.iter().any(|b| b.0 > now),
+            // A replenishing tracker has nothing outstanding once its balance
+            // has recovered to the full burst capacity; while it's still in
+            // deficit, reporting it as stale would let an over-budget
+            // project escape by being dropped and re-created at full balance.
+            BudgetMode::Replenishing { max_budget, .. } => {
+                self.replenished_balance(now) >= max_budget
+            }
+        }
     }
 
     /// Updates the internal state, calculating whether this project exceeds its budget.
     ///
     /// On state update, this will register a "backoff" timer to avoid rapid flip-flopping.
     fn update_aggregated_state(&mut self, now: Instant) -> bool {
+        let total_spent_budget = self.windowed_total(now);
+        self.transition(now, total_spent_budget > self.config.allowed_budget)
+    }
+
+    /// Sums the budget of spends that have not yet expired as of `now`.
+    fn windowed_total(&self, now: Instant) -> f64 {
+        self.budget_buckets
+            .iter()
+            .filter_map(|b| (b.0 > now).then_some(b.1))
+            .sum()
+    }
+
+    /// Applies the backoff/flip-flop protection to a freshly computed
+    /// over/under budget verdict, returning the (possibly held-over) result.
+    fn transition(&mut self, now: Instant, exceeds_budget: bool) -> bool {
         if let Some(deadline) = self.backoff_deadline {
             if deadline > now {
                 return self.exceeds_budget;
@@ -91,15 +318,6 @@ impl ProjectStats {
             self.backoff_deadline = None;
         }
 
-        let lowest_time = now - self.config.budgeting_window;
-        let total_spent_budget: f64 = self
-            .budget_buckets
-            .iter()
-            .filter_map(|b| (b.0 >= lowest_time).then_some(b.1))
-            .sum();
-
-        let exceeds_budget = total_spent_budget > self.config.allowed_budget;
-
         if self.exceeds_budget != exceeds_budget {
             self.exceeds_budget = exceeds_budget;
             self.backoff_deadline = Some(now + self.config.backoff_duration);
@@ -109,6 +327,172 @@ impl ProjectStats {
     }
 }
 
+/// The fixed-point scale used to store spend amounts in
+/// [`AtomicProjectStats`]'s atomics, trading a little precision for the
+/// ability to accumulate with a plain `fetch_add` instead of a CAS loop.
+const ATOMIC_SPEND_SCALE: f64 = 1_000_000.0;
+
+fn to_fixed(amount: f64) -> u64 {
+    (amount * ATOMIC_SPEND_SCALE).round() as u64
+}
+
+fn from_fixed(amount: u64) -> f64 {
+    amount as f64 / ATOMIC_SPEND_SCALE
+}
+
+/// A lock-free sibling of [`ProjectStats`] for sharing a single tracker
+/// across many worker threads.
+///
+/// This collapses the per-bucket [`VecDeque`] history into a single rolling
+/// accumulator covering `budgeting_window`, so all operations take `&self`.
+/// Spend is tracked as a monotonically increasing, never-reset counter
+/// (`total_spent`), and the current window's total is `total_spent` minus a
+/// `window_baseline` snapshot taken when the window last rolled over. This
+/// way a rollover never has to clobber an accumulator that a concurrent
+/// spend is also writing to: every spend is always folded into
+/// `total_spent` (via `fetch_add`, or `try_spend`'s CAS loop) and is never lost, regardless of how it
+/// races against a rollover; at worst a spend that lands right on a window
+/// boundary is counted in whichever of the two windows grabs the baseline
+/// first. This is the same trick Solana's `DataBudget::take` uses to avoid
+/// taking a lock, adapted to also survive a reset racing the accumulation.
+///
+/// The trade-off is that the window is tracked only as a whole: there are no
+/// buckets and no per-spend expiry, so it cannot be inspected at a finer
+/// granularity than `budgeting_window`. The backoff/flip-flop protection that [`ProjectStats`]
+/// provides is not available here, since it would require synchronizing two
+/// pieces of state under one lock-free update. The config's
+/// [`BudgetMode`] is not consulted: spends are always summed over the window
+/// and compared against `allowed_budget`, even for a replenishing config.
+#[derive(Debug)]
+pub struct AtomicProjectStats {
+    /// Configuration that governs the budgeting.
+    config: Arc<BudgetingConfig>,
+
+    /// Total budget ever spent, as a fixed-point integer; only ever grows.
+    total_spent: AtomicU64,
+
+    /// The value `total_spent` had when the current window started.
+    window_baseline: AtomicU64,
+
+    /// Nanoseconds (since the config's [`Timer`](crate::config::Timer) was
+    /// created) at which the current window started.
+    window_start: AtomicU64,
+}
+
+impl AtomicProjectStats {
+    /// Create a new lock-free per-project tracker based on the given [`BudgetingConfig`].
+    pub fn new(config: Arc<BudgetingConfig>) -> Self {
+        Self {
+            config,
+            total_spent: AtomicU64::new(0),
+            window_baseline: AtomicU64::new(0),
+            window_start: AtomicU64::new(0),
+        }
+    }
+
+    /// Checks whether this project exceeds its budget.
+    pub fn exceeds_budget(&self) -> bool {
+        let now = self.config.now();
+        self.roll_window_if_needed(now);
+        self.windowed_spend() > self.config.allowed_budget
+    }
+
+    /// Records spent budget, returning whether the project now exceeds its budget.
+    pub fn record_budget_spend(&self, spent_budget: f64) -> bool {
+        let now = self.config.now();
+        self.roll_window_if_needed(now);
+
+        let amount_fixed = to_fixed(spent_budget);
+        let previous_total_fixed = self.total_spent.fetch_add(amount_fixed, Ordering::SeqCst);
+        let new_total_fixed = previous_total_fixed + amount_fixed;
+
+        let baseline_fixed = self.window_baseline.load(Ordering::SeqCst);
+        from_fixed(new_total_fixed.saturating_sub(baseline_fixed)) > self.config.allowed_budget
+    }
+
+    /// Atomically reserves `amount` against the budget, rejecting the spend
+    /// instead of recording it if the project is already at or over budget.
+    ///
+    /// Like `record_budget_spend`, this never blocks on a lock, and the
+    /// whole check-and-reserve happens as a single CAS loop iteration so
+    /// concurrent callers can't overshoot `allowed_budget` between a check
+    /// and a separate recording call.
+    pub fn try_spend(&self, amount: f64) -> SpendOutcome {
+        let now = self.config.now();
+        self.roll_window_if_needed(now);
+
+        let amount_fixed = to_fixed(amount);
+        let mut total_fixed = self.total_spent.load(Ordering::SeqCst);
+        loop {
+            let baseline_fixed = self.window_baseline.load(Ordering::SeqCst);
+            let windowed = from_fixed(total_fixed.saturating_sub(baseline_fixed));
+
+            if windowed >= self.config.allowed_budget {
+                return SpendOutcome {
+                    accepted: false,
+                    remaining: self.config.allowed_budget - windowed,
+                    hit_zero: false,
+                };
+            }
+
+            let updated_total_fixed = total_fixed + amount_fixed;
+            match self.total_spent.compare_exchange_weak(
+                total_fixed,
+                updated_total_fixed,
+                Ordering::SeqCst,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    let new_windowed = from_fixed(updated_total_fixed.saturating_sub(baseline_fixed));
+                    return SpendOutcome {
+                        accepted: true,
+                        remaining: self.config.allowed_budget - new_windowed,
+                        hit_zero: new_windowed > self.config.allowed_budget,
+                    };
+                }
+                Err(actual_total_fixed) => total_fixed = actual_total_fixed,
+            }
+        }
+    }
+
+    /// Returns the budget spent in the window currently tracked by `window_baseline`.
+    fn windowed_spend(&self) -> f64 {
+        let total_fixed = self.total_spent.load(Ordering::SeqCst);
+        let baseline_fixed = self.window_baseline.load(Ordering::SeqCst);
+        from_fixed(total_fixed.saturating_sub(baseline_fixed))
+    }
+
+    /// If the current window has expired, rolls it over by snapshotting
+    /// `total_spent` into `window_baseline`.
+    ///
+    /// The snapshot is taken *after* winning the `window_start` CAS, but
+    /// unlike resetting an accumulator to zero, snapshotting a
+    /// monotonically increasing counter can't lose a concurrent spend: a
+    /// spend racing this rollover either lands in `total_spent` before the
+    /// snapshot (so it's folded into the new baseline, i.e. not counted in
+    /// the new window) or after (so it's counted), but it is always folded
+    /// into `total_spent` itself via `fetch_add`/CAS and so is never
+    /// dropped.
+    fn roll_window_if_needed(&self, now: Instant) {
+        let now_nanos = self.config.nanos_since_start(now);
+        let window_nanos = self.config.budgeting_window.as_nanos() as u64;
+        let window_start = self.window_start.load(Ordering::SeqCst);
+
+        if now_nanos.saturating_sub(window_start) < window_nanos {
+            return;
+        }
+
+        if self
+            .window_start
+            .compare_exchange(window_start, now_nanos, Ordering::SeqCst, Ordering::Relaxed)
+            .is_ok()
+        {
+            let total_fixed = self.total_spent.load(Ordering::SeqCst);
+            self.window_baseline.store(total_fixed, Ordering::SeqCst);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::time::Duration;
@@ -163,7 +547,217 @@ mod tests {
         // the backoff deadline has passed, we are unblocked
         assert!(!stats.exceeds_budget());
 
-        // after *another* backoff, these stats are stale
+        // we just flipped state again, so we are back in backoff and not yet stale
+        assert!(!stats.is_stale(clock.now()));
+    }
+
+    #[test]
+    fn test_is_stale_window_mode() {
+        let (clock, mock) = Clock::mock();
+
+        let config = BudgetingConfig::new(
+            Duration::from_secs(1),
+            Duration::from_secs(5),
+            Duration::from_secs(1),
+            100.,
+        )
+        .with_timer(Timer::new(clock.clone()));
+
+        let mut stats = ProjectStats::new(Arc::new(config));
+
+        // Well under budget, so this never flips `exceeds_budget` and so
+        // never arms the backoff deadline.
+        assert!(!stats.record_budget_spend(10.));
         assert!(!stats.is_stale(clock.now()));
+
+        // Advance past the bucket's expiry; with no backoff pending, every
+        // recorded spend has expired and the tracker is reclaimable.
+        mock.increment(Duration::from_secs(6));
+        assert!(stats.is_stale(clock.now()));
+    }
+
+    #[test]
+    fn test_custom_expiry_survives_eviction() {
+        let (clock, mock) = Clock::mock();
+
+        // Only 3 buckets fit into the window, so plain default-expiry spends
+        // would normally start evicting each other after the third one.
+        let config = BudgetingConfig::new(
+            Duration::from_millis(1),
+            Duration::from_secs(3),
+            Duration::from_secs(1),
+            1000.,
+        )
+        .with_timer(Timer::new(clock.clone()));
+
+        let mut stats = ProjectStats::new(Arc::new(config));
+
+        // A long-lived batch spend that should count against the budget for an hour.
+        stats.record_budget_spend_with_expiry(500., Duration::from_secs(3600));
+
+        // Enough short-lived, default-expiry spends to fill and evict from
+        // every bucket slot several times over.
+        for _ in 0..5 {
+            mock.increment(Duration::from_secs(1));
+            stats.record_budget_spend(1.);
+        }
+
+        // The batch spend was pushed first (and so sits at the back of the
+        // bucket queue), but it expires far later than the other buckets and
+        // must not be evicted ahead of them.
+        assert_eq!(stats.windowed_total(clock.now()), 502.);
+    }
+
+    #[test]
+    fn test_try_spend_windowed() {
+        let (clock, _mock) = Clock::mock();
+
+        let config = BudgetingConfig::new(
+            Duration::from_secs(10),
+            Duration::from_secs(5),
+            Duration::from_secs(1),
+            100.,
+        )
+        .with_timer(Timer::new(clock.clone()));
+
+        let mut stats = ProjectStats::new(Arc::new(config));
+
+        let outcome = stats.try_spend(60.);
+        assert_eq!(
+            outcome,
+            SpendOutcome {
+                accepted: true,
+                remaining: 40.,
+                hit_zero: false,
+            }
+        );
+
+        // This call is the one that pushes the project from under to over budget.
+        let outcome = stats.try_spend(45.);
+        assert_eq!(
+            outcome,
+            SpendOutcome {
+                accepted: true,
+                remaining: -5.,
+                hit_zero: true,
+            }
+        );
+
+        // Already over budget, so this spend is rejected and not recorded.
+        let outcome = stats.try_spend(5.);
+        assert_eq!(
+            outcome,
+            SpendOutcome {
+                accepted: false,
+                remaining: -5.,
+                hit_zero: false,
+            }
+        );
+        assert_eq!(stats.windowed_total(clock.now()), 105.);
+    }
+
+    #[test]
+    fn test_replenishing_budget() {
+        let (clock, mock) = Clock::mock();
+
+        let config = BudgetingConfig::new(
+            Duration::from_secs(10),
+            Duration::from_secs(5),
+            Duration::from_secs(1),
+            100.,
+        )
+        .with_replenishing(10., 50.)
+        .with_timer(Timer::new(clock.clone()));
+
+        let mut stats = ProjectStats::new(Arc::new(config));
+
+        // Starts out at the burst cap, so this spend is easily affordable.
+        assert!(!stats.record_budget_spend(40.));
+
+        mock.increment(Duration::from_secs(2));
+
+        // Replenishment accrual: 10 + 10/s * 2s = 30, still under budget.
+        assert!(!stats.exceeds_budget());
+
+        mock.increment(Duration::from_secs(10));
+
+        // Accrual would overshoot the burst cap (30 + 10/s * 10s = 130), so
+        // it must clamp to max_budget instead.
+        assert!(!stats.exceeds_budget());
+
+        // Replenished balance clamps to 50, then this spend drives it negative.
+        assert!(stats.record_budget_spend(70.));
+
+        mock.increment(Duration::from_secs(5));
+
+        // Replenishment since the last spend (-20 + 10/s * 5s = 30) would
+        // already put the project back under budget, but backoff holds the
+        // over-budget verdict until its deadline passes.
+        assert!(stats.exceeds_budget());
+
+        mock.increment(Duration::from_secs(6));
+
+        // Backoff has now expired, so the transition can finally apply.
+        assert!(!stats.exceeds_budget());
+    }
+
+    #[test]
+    fn test_atomic_project_stats() {
+        let (clock, mock) = Clock::mock();
+
+        let config = BudgetingConfig::new(
+            Duration::from_secs(10),
+            Duration::from_secs(5),
+            Duration::from_secs(1),
+            100.,
+        )
+        .with_timer(Timer::new(clock.clone()));
+
+        let stats = AtomicProjectStats::new(Arc::new(config));
+
+        // Accumulation across separate calls goes through the same
+        // fetch_add/CAS accumulator, so both calls must be reflected.
+        assert!(!stats.record_budget_spend(40.));
+        assert!(stats.record_budget_spend(70.));
+        assert!(stats.exceeds_budget());
+
+        // The window has now elapsed, so the next operation should roll it
+        // over: the total spent so far becomes the new baseline, and the
+        // project is back under budget.
+        mock.increment(Duration::from_secs(6));
+        assert!(!stats.exceeds_budget());
+
+        // Accumulation via `try_spend`'s CAS loop within the new window.
+        let outcome = stats.try_spend(50.);
+        assert_eq!(
+            outcome,
+            SpendOutcome {
+                accepted: true,
+                remaining: 50.,
+                hit_zero: false,
+            }
+        );
+
+        // This call is the one that pushes the project from under to over budget.
+        let outcome = stats.try_spend(60.);
+        assert_eq!(
+            outcome,
+            SpendOutcome {
+                accepted: true,
+                remaining: -10.,
+                hit_zero: true,
+            }
+        );
+
+        // Already over budget, so this spend is rejected outright.
+        let outcome = stats.try_spend(10.);
+        assert_eq!(
+            outcome,
+            SpendOutcome {
+                accepted: false,
+                remaining: -10.,
+                hit_zero: false,
+            }
+        );
     }
 }